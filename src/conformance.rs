@@ -0,0 +1,202 @@
+//! Conformance test runner for `run_onnx_model` / `collapse_truth_waveform`,
+//! modeled on Boa's test262 harness: walk a directory of declarative case
+//! files, run each through a `HalTrait`, and classify the result.
+//!
+//! A case file is a flat `key = value` list:
+//!
+//! ```text
+//! op = run_onnx_model
+//! model = models/identity.onnx
+//! input = zeros(4,4)
+//! expect_output = processed
+//! ```
+//!
+//! `op` defaults to `run_onnx_model` (fields `model`/`input`) and can also
+//! be `collapse_truth_waveform` (fields `emotion`/`mode`/`time`). Exactly
+//! one of `expect_output`/`expect_error` should be set, matched as a
+//! substring of the HAL's `Ok`/`Err` result respectively.
+
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::panic::{self, AssertUnwindSafe};
+use std::path::{Path, PathBuf};
+
+use crate::hal::{HalTrait, TensorData};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CaseOutcome {
+    Passed,
+    Failed,
+    Ignored,
+    Panicked,
+}
+
+pub struct CaseReport {
+    pub name: String,
+    pub outcome: CaseOutcome,
+    pub detail: Option<String>,
+}
+
+pub struct ConformanceSummary {
+    pub reports: Vec<CaseReport>,
+}
+
+impl ConformanceSummary {
+    pub fn counts(&self) -> (usize, usize, usize, usize) {
+        let mut passed = 0;
+        let mut failed = 0;
+        let mut ignored = 0;
+        let mut panicked = 0;
+        for report in &self.reports {
+            match report.outcome {
+                CaseOutcome::Passed => passed += 1,
+                CaseOutcome::Failed => failed += 1,
+                CaseOutcome::Ignored => ignored += 1,
+                CaseOutcome::Panicked => panicked += 1,
+            }
+        }
+        (passed, failed, ignored, panicked)
+    }
+}
+
+enum Operation {
+    RunOnnxModel { model_path: String, input_info: String },
+    CollapseTruth { emotion: String, mode: String, time: String },
+}
+
+enum Expectation {
+    OutputContains(String),
+    ErrorContains(String),
+}
+
+struct TestCase {
+    name: String,
+    operation: Operation,
+    expectation: Expectation,
+}
+
+fn parse_case_file(path: &Path, name: &str) -> Result<TestCase, String> {
+    let contents = fs::read_to_string(path).map_err(|e| format!("could not read case file: {}", e))?;
+
+    let mut fields: HashMap<String, String> = HashMap::new();
+    for line in contents.lines() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        if let Some((key, value)) = line.split_once('=') {
+            fields.insert(key.trim().to_string(), value.trim().to_string());
+        }
+    }
+
+    let operation = match fields.get("op").map(String::as_str) {
+        Some("collapse_truth_waveform") => Operation::CollapseTruth {
+            emotion: fields.get("emotion").cloned().unwrap_or_default(),
+            mode: fields.get("mode").cloned().unwrap_or_default(),
+            time: fields.get("time").cloned().unwrap_or_default(),
+        },
+        _ => Operation::RunOnnxModel {
+            model_path: fields.get("model").cloned().unwrap_or_default(),
+            input_info: fields.get("input").cloned().unwrap_or_default(),
+        },
+    };
+
+    let expectation = match fields.get("expect_error") {
+        Some(substr) => Expectation::ErrorContains(substr.clone()),
+        None => Expectation::OutputContains(fields.get("expect_output").cloned().unwrap_or_default()),
+    };
+
+    Ok(TestCase { name: name.to_string(), operation, expectation })
+}
+
+fn execute(case: &TestCase, hal: &(dyn HalTrait + Send + Sync)) -> Result<String, String> {
+    match &case.operation {
+        Operation::RunOnnxModel { model_path, input_info } => {
+            let input = TensorData { info: input_info.clone() };
+            hal.run_onnx_model(model_path, &input).map(|output| output.info)
+        }
+        Operation::CollapseTruth { emotion, mode, time } => hal.collapse_truth_waveform(emotion, mode, time),
+    }
+}
+
+fn run_case(case: TestCase, hal: &(dyn HalTrait + Send + Sync)) -> CaseReport {
+    let name = case.name.clone();
+    match panic::catch_unwind(AssertUnwindSafe(|| execute(&case, hal))) {
+        Ok(Ok(output)) => {
+            let passed = matches!(&case.expectation, Expectation::OutputContains(substr) if output.contains(substr.as_str()));
+            CaseReport { name, outcome: if passed { CaseOutcome::Passed } else { CaseOutcome::Failed }, detail: Some(output) }
+        }
+        Ok(Err(error)) => {
+            let passed = matches!(&case.expectation, Expectation::ErrorContains(substr) if error.contains(substr.as_str()));
+            CaseReport { name, outcome: if passed { CaseOutcome::Passed } else { CaseOutcome::Failed }, detail: Some(error) }
+        }
+        Err(_) => CaseReport { name, outcome: CaseOutcome::Panicked, detail: Some("case panicked during execution".to_string()) },
+    }
+}
+
+fn load_ignore_list(suite_dir: &Path) -> HashSet<String> {
+    fs::read_to_string(suite_dir.join("test_ignore.txt"))
+        .map(|contents| {
+            contents
+                .lines()
+                .map(str::trim)
+                .filter(|line| !line.is_empty() && !line.starts_with('#'))
+                .map(str::to_string)
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// Walks `suite_dir` for `*.case` files, runs each through `hal`, and
+/// returns a report per case. One case panicking does not abort the run.
+pub fn run_suite(suite_dir: &str, hal: &(dyn HalTrait + Send + Sync)) -> Result<ConformanceSummary, String> {
+    let dir = Path::new(suite_dir);
+    let ignore_list = load_ignore_list(dir);
+
+    let mut case_paths: Vec<PathBuf> = fs::read_dir(dir)
+        .map_err(|e| format!("could not read suite dir '{}': {}", suite_dir, e))?
+        .filter_map(|entry| entry.ok())
+        .map(|entry| entry.path())
+        .filter(|path| path.extension().map(|ext| ext == "case").unwrap_or(false))
+        .collect();
+    case_paths.sort();
+
+    let mut reports = Vec::with_capacity(case_paths.len());
+    for path in case_paths {
+        let name = path.file_stem().and_then(|stem| stem.to_str()).unwrap_or("unnamed").to_string();
+
+        if ignore_list.contains(&name) {
+            reports.push(CaseReport { name, outcome: CaseOutcome::Ignored, detail: None });
+            continue;
+        }
+
+        reports.push(match parse_case_file(&path, &name) {
+            Ok(case) => run_case(case, hal),
+            Err(error) => CaseReport { name, outcome: CaseOutcome::Failed, detail: Some(error) },
+        });
+    }
+
+    Ok(ConformanceSummary { reports })
+}
+
+/// Writes a machine-readable results file (summary counts plus per-case
+/// status) alongside the suite, so CI can diff conformance runs over time.
+pub fn write_results_file(suite_dir: &str, summary: &ConformanceSummary) -> Result<PathBuf, String> {
+    let (passed, failed, ignored, panicked) = summary.counts();
+
+    let mut contents = format!(
+        "passed={} failed={} ignored={} panicked={} total={}\n",
+        passed,
+        failed,
+        ignored,
+        panicked,
+        summary.reports.len()
+    );
+    for report in &summary.reports {
+        contents.push_str(&format!("{}: {:?}\n", report.name, report.outcome));
+    }
+
+    let results_path = Path::new(suite_dir).join("conformance_results.txt");
+    fs::write(&results_path, contents).map_err(|e| format!("could not write results file: {}", e))?;
+    Ok(results_path)
+}
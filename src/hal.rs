@@ -1,44 +1,239 @@
 use std::collections::HashMap;
 
+use crate::digest::{self, sha256, twox128_like};
+use crate::manifest;
+
+/// Prefix used when deriving manifest lookup keys, analogous to the
+/// module-name prefix Substrate mixes into its storage key derivation.
+const MANIFEST_KEY_PREFIX: &[u8] = b"soul_cli/github_manifest";
+
 // Placeholder for actual tensor data structures
 #[derive(Debug)]
 pub struct TensorData {
     pub info: String,
 }
 
-pub trait HalTrait {
+/// A single entry in the module manifest's Merkle-chained ledger.
+///
+/// Each entry commits to the module's content hash *and* to the running
+/// chain root at the point it was appended (`prev_root`), so re-folding the
+/// whole ledger from genesis detects tampering with any earlier entry, not
+/// just the one being looked up.
+///
+/// `chain_name`, `manifest_version` and `protocol_version` back module
+/// compatibility negotiation (see [`HalTrait::negotiate_module`]), mirroring
+/// the fields Tezos carries in its `NetworkVersion` handshake.
+#[derive(Debug, Clone)]
+pub struct ManifestEntry {
+    pub module_name: String,
+    pub source_url: String,
+    pub content_hash: [u8; 32],
+    pub chain_name: String,
+    pub manifest_version: u16,
+    pub protocol_version: u16,
+    pub prev_root: [u8; 32],
+    pub entry_hash: [u8; 32],
+}
+
+/// A successfully negotiated module handshake.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Accepted {
+    pub chain_name: String,
+    pub manifest_version: u16,
+    pub protocol_version: u16,
+}
+
+/// A rejected module handshake, carrying a human-readable motive the way
+/// Tezos's nack-carrying-a-motive does.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Nack {
+    UnknownModule,
+    ManifestTooOld { required: u16, peer: u16 },
+    ProtocolMismatch { supported: Vec<u16> },
+}
+
+impl std::fmt::Display for Nack {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Nack::UnknownModule => write!(f, "module not present in this HAL's manifest"),
+            Nack::ManifestTooOld { required, peer } => {
+                write!(f, "peer manifest version {} is older than the required {}", peer, required)
+            }
+            Nack::ProtocolMismatch { supported } => {
+                write!(f, "protocol version not supported; this HAL supports {:?}", supported)
+            }
+        }
+    }
+}
+
+/// Folds a chain entry: `entry_hash = H(prev_root || module_name || content_hash)`.
+fn fold_entry(prev_root: &[u8; 32], module_name: &str, content_hash: &[u8; 32]) -> [u8; 32] {
+    let mut buf = Vec::with_capacity(32 + module_name.len() + 32);
+    buf.extend_from_slice(prev_root);
+    buf.extend_from_slice(module_name.as_bytes());
+    buf.extend_from_slice(content_hash);
+    sha256(&buf)
+}
+
+pub trait HalTrait: Send + Sync {
     fn get_system_status(&self) -> Result<String, String>;
     fn verify_module_signature(&self, module_name: &str, signature_source: &str) -> Result<bool, String>;
     fn get_emotional_map(&self) -> Result<Vec<String>, String>;
     fn collapse_truth_waveform(&self, emotion: &str, mode: &str, time_vector: &str) -> Result<String, String>;
     fn initialize_npu(&self) -> Result<String, String>;
     fn run_onnx_model(&self, model_path: &str, inputs: &TensorData) -> Result<TensorData, String>;
+    /// Computes the content digest for a module's bytes, used both to
+    /// populate and to verify manifest entries.
+    fn hash_module(&self, module_name: &str, bytes: &[u8]) -> Result<[u8; 32], String>;
+    /// Re-folds the whole manifest ledger from genesis and returns the final
+    /// root, or the index/name of the first entry whose chain link doesn't
+    /// verify.
+    fn verify_ledger_chain(&self) -> Result<[u8; 32], (usize, String)>;
+    /// Names of every module currently in the manifest, in ledger order.
+    fn manifest_module_names(&self) -> Vec<String>;
+    /// Negotiates whether a module build at `peer_manifest_version` /
+    /// `peer_protocol_version` would be accepted by this HAL, without
+    /// actually loading it.
+    fn negotiate_module(
+        &self,
+        module_name: &str,
+        peer_manifest_version: u16,
+        peer_protocol_version: u16,
+    ) -> Result<Accepted, Nack>;
 }
 
 pub struct MockHal {
-    github_manifest: HashMap<String, (String, String)>,
+    manifest: Vec<ManifestEntry>,
+    key_index: HashMap<[u8; 16], usize>,
+}
+
+/// Simulated "bytes on disk" for a module, fetched at verification time.
+///
+/// `UserInterfaceModule` deliberately diverges from the bytes it was
+/// published with, so the mock continues to demonstrate a failing
+/// verification the way the old hardcoded-mismatch constant did.
+fn simulated_local_bytes(module_name: &str) -> Vec<u8> {
+    if module_name == "UserInterfaceModule" {
+        format!("{} module payload v1 (tampered)", module_name).into_bytes()
+    } else {
+        format!("{} module payload v1", module_name).into_bytes()
+    }
+}
+
+/// A parsed manifest entry, not yet folded into the ledger.
+struct SeedEntry {
+    module_name: String,
+    source_url: String,
+    content_hash: [u8; 32],
+    chain_name: String,
+    manifest_version: u16,
+    protocol_version: u16,
 }
 
 impl MockHal {
+    /// The built-in module set, used when no manifest config file is given
+    /// (or it can't be loaded). Content hashes are computed from the
+    /// "published" bytes each module's own `simulated_local_bytes` is
+    /// expected to match.
+    fn default_entries() -> Vec<SeedEntry> {
+        let seed_modules: [(&str, &str); 4] = [
+            ("SoulOS_Core", "gh://soulware/core/v1.0"),
+            ("TensorMemoryDriver", "gh://soulware/tensor/v0.9"),
+            ("EmotionalResonanceEngine", "gh://soulware/ere/v0.5"),
+            ("UserInterfaceModule", "gh://soulware/ui/v1.1"),
+        ];
+
+        seed_modules
+            .into_iter()
+            .map(|(module_name, source_url)| {
+                let published_bytes = format!("{} module payload v1", module_name).into_bytes();
+                SeedEntry {
+                    module_name: module_name.to_string(),
+                    source_url: source_url.to_string(),
+                    content_hash: sha256(&published_bytes),
+                    chain_name: "souldos_mainnet".to_string(),
+                    manifest_version: 1,
+                    protocol_version: 1,
+                }
+            })
+            .collect()
+    }
+
+    /// Folds a list of seed entries into a Merkle-chained ledger plus its
+    /// key-derived lookup index.
+    fn build_ledger(entries: Vec<SeedEntry>) -> (Vec<ManifestEntry>, HashMap<[u8; 16], usize>) {
+        let mut manifest = Vec::with_capacity(entries.len());
+        let mut key_index = HashMap::with_capacity(entries.len());
+        let mut root = [0u8; 32];
+
+        for entry in entries {
+            let entry_hash = fold_entry(&root, &entry.module_name, &entry.content_hash);
+
+            let key = twox128_like(MANIFEST_KEY_PREFIX, entry.module_name.as_bytes());
+            key_index.insert(key, manifest.len());
+
+            manifest.push(ManifestEntry {
+                module_name: entry.module_name,
+                source_url: entry.source_url,
+                content_hash: entry.content_hash,
+                chain_name: entry.chain_name,
+                manifest_version: entry.manifest_version,
+                protocol_version: entry.protocol_version,
+                prev_root: root,
+                entry_hash,
+            });
+
+            root = entry_hash;
+        }
+
+        (manifest, key_index)
+    }
+
     pub fn new() -> Self {
-        let mut manifest = HashMap::new();
-        manifest.insert(
-            "SoulOS_Core".to_string(),
-            ("hash_core_123_abc".to_string(), "gh://soulware/core/v1.0".to_string()),
-        );
-        manifest.insert(
-            "TensorMemoryDriver".to_string(),
-            ("hash_tensor_xyz_789".to_string(), "gh://soulware/tensor/v0.9".to_string()),
-        );
-        manifest.insert(
-            "EmotionalResonanceEngine".to_string(),
-            ("hash_ere_qwerty_456".to_string(), "gh://soulware/ere/v0.5".to_string()),
-        );
-        manifest.insert(
-            "UserInterfaceModule".to_string(),
-            ("hash_ui_zxcv_321".to_string(), "gh://soulware/ui/v1.1".to_string()),
-        );
-        MockHal { github_manifest: manifest }
+        let (manifest, key_index) = Self::build_ledger(Self::default_entries());
+        MockHal { manifest, key_index }
+    }
+
+    /// Loads the module manifest from an external config file at `path`,
+    /// falling back to the built-in defaults when it's absent or empty so
+    /// a missing `--manifest` target degrades gracefully rather than
+    /// leaving the HAL with no modules at all.
+    pub fn from_manifest(path: &str) -> Self {
+        let entries = match manifest::load(path) {
+            Ok(configs) if !configs.is_empty() => configs
+                .into_iter()
+                .filter_map(|config| match digest::from_hex(&config.content_hash_hex) {
+                    Ok(content_hash) => Some(SeedEntry {
+                        module_name: config.module_name,
+                        source_url: config.source_url,
+                        content_hash,
+                        chain_name: config.chain_name,
+                        manifest_version: config.manifest_version,
+                        protocol_version: config.protocol_version,
+                    }),
+                    Err(e) => {
+                        eprintln!("Warning: skipping manifest entry '{}': {}", config.module_name, e);
+                        None
+                    }
+                })
+                .collect(),
+            Ok(_) => {
+                println!("Manifest file '{}' has no entries; using built-in defaults.", path);
+                Self::default_entries()
+            }
+            Err(e) => {
+                println!("Could not load manifest '{}' ({}); using built-in defaults.", path, e);
+                Self::default_entries()
+            }
+        };
+
+        let (manifest, key_index) = Self::build_ledger(entries);
+        MockHal { manifest, key_index }
+    }
+
+    fn find_entry(&self, module_name: &str) -> Option<&ManifestEntry> {
+        let key = twox128_like(MANIFEST_KEY_PREFIX, module_name.as_bytes());
+        self.key_index.get(&key).map(|&i| &self.manifest[i])
     }
 }
 
@@ -50,24 +245,45 @@ impl HalTrait for MockHal {
     fn verify_module_signature(&self, module_name: &str, signature_source: &str) -> Result<bool, String> {
         if signature_source == "GitHubBlockchainLedger (Simulated)" {
             println!("MockHAL: Accessing GitHub manifest for module '{}' via '{}'...", module_name, signature_source);
-            match self.github_manifest.get(module_name) {
-                Some((expected_hash, github_url)) => {
-                    println!("MockHAL: Found entry. Expected signature (from {}): {}", github_url, expected_hash);
-                    
-                    let local_calculated_hash = if module_name == "UserInterfaceModule" {
-                        "hash_ui_zxcv_FAIL".to_string() // Simulate failure for this module
-                    } else {
-                        expected_hash.clone() // Simulate success for others
-                    };
-                    
-                    println!("MockHAL: Calculated local signature for '{}': {}", module_name, local_calculated_hash);
-
-                    if local_calculated_hash == *expected_hash {
-                        println!("MockHAL: Signature VERIFIED for '{}'.", module_name);
-                        Ok(true)
-                    } else {
-                        println!("MockHAL: SIGNATURE MISMATCH for '{}'! Expected '{}', got '{}'.", module_name, expected_hash, local_calculated_hash);
-                        Ok(false)
+            match self.find_entry(module_name) {
+                Some(entry) => {
+                    println!(
+                        "MockHAL: Found entry. Expected content hash (from {}): {}",
+                        entry.source_url,
+                        digest::to_hex(&entry.content_hash)
+                    );
+
+                    let local_bytes = simulated_local_bytes(module_name);
+                    let local_hash = self.hash_module(module_name, &local_bytes)?;
+                    println!(
+                        "MockHAL: Calculated local content hash for '{}': {}",
+                        module_name,
+                        digest::to_hex(&local_hash)
+                    );
+
+                    if local_hash != entry.content_hash {
+                        println!(
+                            "MockHAL: CONTENT HASH MISMATCH for '{}'! Expected '{}', got '{}'.",
+                            module_name,
+                            digest::to_hex(&entry.content_hash),
+                            digest::to_hex(&local_hash)
+                        );
+                        return Ok(false);
+                    }
+
+                    match self.verify_ledger_chain() {
+                        Ok(root) => {
+                            println!("MockHAL: Ledger chain intact. Root: {}", digest::to_hex(&root));
+                            println!("MockHAL: Signature VERIFIED for '{}'.", module_name);
+                            Ok(true)
+                        }
+                        Err((idx, broken_module)) => {
+                            println!(
+                                "MockHAL: LEDGER TAMPERED at entry #{} ('{}')! Refusing to trust '{}'.",
+                                idx, broken_module, module_name
+                            );
+                            Ok(false)
+                        }
                     }
                 }
                 None => {
@@ -80,7 +296,7 @@ impl HalTrait for MockHal {
             // For internal checks, we can still check against our "known good" versions from the manifest for consistency if desired,
             // or just always return true for this simulation.
             // For this task, modules like "RustHAL_Interface" are not in the github_manifest, so they need separate handling.
-            if self.github_manifest.contains_key(module_name) || module_name == "RustHAL_Interface" {
+            if self.find_entry(module_name).is_some() || module_name == "RustHAL_Interface" {
                  println!("MockHAL: Core module '{}' integrity VERIFIED internally.", module_name);
                  Ok(true)
             } else {
@@ -108,8 +324,54 @@ impl HalTrait for MockHal {
 
     fn run_onnx_model(&self, model_path: &str, inputs: &TensorData) -> Result<TensorData, String> {
         println!("MockHAL: Running ONNX model {} with input info: '{}'", model_path, inputs.info);
-        Ok(TensorData { 
-            info: format!("MockHAL: ONNX model {} processed with input {}", model_path, inputs.info) 
+        Ok(TensorData {
+            info: format!("MockHAL: ONNX model {} processed with input {}", model_path, inputs.info)
+        })
+    }
+
+    fn hash_module(&self, _module_name: &str, bytes: &[u8]) -> Result<[u8; 32], String> {
+        Ok(sha256(bytes))
+    }
+
+    fn verify_ledger_chain(&self) -> Result<[u8; 32], (usize, String)> {
+        let mut root = [0u8; 32];
+        for (i, entry) in self.manifest.iter().enumerate() {
+            if entry.prev_root != root {
+                return Err((i, entry.module_name.clone()));
+            }
+            let expected = fold_entry(&root, &entry.module_name, &entry.content_hash);
+            if expected != entry.entry_hash {
+                return Err((i, entry.module_name.clone()));
+            }
+            root = entry.entry_hash;
+        }
+        Ok(root)
+    }
+
+    fn manifest_module_names(&self) -> Vec<String> {
+        self.manifest.iter().map(|entry| entry.module_name.clone()).collect()
+    }
+
+    fn negotiate_module(
+        &self,
+        module_name: &str,
+        peer_manifest_version: u16,
+        peer_protocol_version: u16,
+    ) -> Result<Accepted, Nack> {
+        let entry = self.find_entry(module_name).ok_or(Nack::UnknownModule)?;
+
+        if peer_manifest_version < entry.manifest_version {
+            return Err(Nack::ManifestTooOld { required: entry.manifest_version, peer: peer_manifest_version });
+        }
+
+        if peer_protocol_version != entry.protocol_version {
+            return Err(Nack::ProtocolMismatch { supported: vec![entry.protocol_version] });
+        }
+
+        Ok(Accepted {
+            chain_name: entry.chain_name.clone(),
+            manifest_version: entry.manifest_version,
+            protocol_version: entry.protocol_version,
         })
     }
 }
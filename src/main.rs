@@ -1,16 +1,32 @@
 use std::io::{self, Write};
+use std::sync::Arc;
+use std::thread;
+use std::time::Duration;
 use chrono::Local;
 use clap::{Parser, Subcommand, CommandFactory};
 use human_panic::setup_panic;
 
+mod conformance;
+mod digest;
 mod hal;
+mod manifest;
+mod suggest;
+mod verify_queue;
+use conformance::CaseOutcome;
 use hal::{HalTrait, MockHal, TensorData};
+use suggest::closest_match;
+use verify_queue::{VerificationResult, VerifyQueue};
 
 #[derive(Parser)]
 #[command(name = "SoulDOS", version = "0.0.1-alpha", about = "CLI for SoulWare OS", long_about = None)]
 struct Cli {
     #[command(subcommand)]
     command: Option<Commands>,
+
+    /// Path to a module manifest config file; falls back to the built-in
+    /// defaults when not given or unreadable.
+    #[arg(long, global = true)]
+    manifest: Option<String>,
 }
 
 #[derive(Subcommand)]
@@ -23,17 +39,14 @@ enum Commands {
     /// Display the current time
     Time,
     /// Clear the terminal screen
-    Cls,
-    /// Clear the terminal screen
+    #[command(alias = "cls")]
     Clear,
     /// List directory contents or module status (placeholder)
+    #[command(alias = "dir")]
     Ls,
-    /// List directory contents or module status (placeholder)
-    Dir,
     /// Display system status or memory resonance
+    #[command(alias = "mem")]
     Status,
-    /// Display system status or memory resonance
-    Mem,
     /// Check integrity of a specific module
     CheckModuleIntegrity { module_name: Option<String> },
     /// Perform system integrity check
@@ -55,9 +68,18 @@ enum Commands {
         model_path: String,
         input_info: String,
     },
+    /// Run a directory of conformance test cases against the HAL
+    RunConformance { suite_dir: String },
+    /// Check whether a module build at the given manifest/protocol version
+    /// would be accepted by this HAL
+    NegotiateModule {
+        module_name: String,
+        manifest_version: u16,
+        protocol_version: u16,
+    },
 }
 
-fn print_module_integrity_status(hal: &impl HalTrait, module_name: &str, manifest: &str) {
+fn print_module_integrity_status(hal: &dyn HalTrait, module_name: &str, manifest: &str) {
     match hal.verify_module_signature(module_name, manifest) {
         Ok(true) => println!("  Core module '{}' integrity: VERIFIED internally.", module_name),
         Ok(false) => println!("  Core module '{}' integrity: VERIFICATION FAILED internally.", module_name),
@@ -65,18 +87,31 @@ fn print_module_integrity_status(hal: &impl HalTrait, module_name: &str, manifes
     }
 }
 
-fn print_module_integrity_status_for_command(hal: &impl HalTrait, module_name: &str, manifest: &str) {
-    print!("  Checking '{}' (source: {})... ", module_name, manifest);
-    io::stdout().flush().unwrap();
-    match hal.verify_module_signature(module_name, manifest) {
-        Ok(true) => println!("VERIFIED"),
-        Ok(false) => println!("FAILED"),
-        Err(e) => println!("ERROR ({})", e),
+/// Prints a verification outcome gathered by a [`VerifyQueue`] worker as a
+/// single `Checking '<module>'... <status>` line.
+fn print_verification_result(module_name: &str, result: Option<&VerificationResult>) {
+    print!("  Checking '{}'... ", module_name);
+    match result.map(|r| &r.outcome) {
+        Some(Ok(true)) => println!("VERIFIED"),
+        Some(Ok(false)) => println!("FAILED"),
+        Some(Err(e)) => println!("ERROR ({})", e),
+        None => println!("ERROR (missing from verification queue results)"),
     }
 }
 
+/// Looks up the closest known command or alias to `typed`, for the
+/// REPL's "did you mean" hint on a failed parse.
+fn suggest_command(typed: &str) -> Option<String> {
+    let command = Cli::command();
+    let mut candidates = Vec::new();
+    for subcommand in command.get_subcommands() {
+        candidates.push(subcommand.get_name().to_string());
+        candidates.extend(subcommand.get_all_aliases().map(|alias| alias.to_string()));
+    }
+    closest_match(typed, candidates.iter().map(String::as_str)).map(str::to_string)
+}
 
-fn handle_command(command_enum: Commands, hal: &impl HalTrait) {
+fn handle_command(command_enum: Commands, hal: &Arc<dyn HalTrait + Send + Sync>) {
     match command_enum {
         // Case for Commands::Help removed
         Commands::Ver => {
@@ -84,14 +119,14 @@ fn handle_command(command_enum: Commands, hal: &impl HalTrait) {
         }
         Commands::Date => println!("{}", Local::now().format("%Y-%m-%d").to_string()),
         Commands::Time => println!("{}", Local::now().format("%H:%M:%S").to_string()),
-        Commands::Cls | Commands::Clear => {
+        Commands::Clear => {
             print!("\x1B[2J\x1B[H");
             io::stdout().flush().unwrap();
         }
-        Commands::Ls | Commands::Dir => {
+        Commands::Ls => {
             println!("Placeholder: Listing directory contents or module status...");
         }
-        Commands::Status | Commands::Mem => {
+        Commands::Status => {
             println!("\nFetching system status...");
             match hal.get_system_status() {
                 Ok(status) => println!("System Status: {}", status),
@@ -99,29 +134,79 @@ fn handle_command(command_enum: Commands, hal: &impl HalTrait) {
             }
         }
         Commands::CheckModuleIntegrity { module_name } => {
-            if let Some(name) = module_name {
+            let names = match module_name {
+                Some(name) => vec![name],
+                None => {
+                    println!("\nNo module specified; checking every module in the manifest...");
+                    hal.manifest_module_names()
+                }
+            };
+
+            for name in names {
                 println!("\nChecking integrity of module: '{}'...", name);
                 match hal.verify_module_signature(&name, "GitHubBlockchainLedger (Simulated)") {
                     Ok(true) => println!("Module '{}' integrity: VERIFIED", name),
                     Ok(false) => println!("Module '{}' integrity: VERIFICATION FAILED", name),
                     Err(e) => println!("Error checking module '{}' integrity: {}", name, e),
                 }
-            } else {
-                println!("Usage: check-module-integrity <module_name>");
             }
         }
         Commands::SystemIntegrityCheck => {
             println!("\nPerforming System Integrity Check...");
 
+            const INTERNAL_MODULES: &[&str] = &["SoulOS_Core", "TensorMemoryDriver", "RustHAL_Interface"];
+            const GITHUB_SOURCE: &str = "GitHubBlockchainLedger (Simulated)";
+
+            // The GitHub-ledger group is whatever the loaded manifest
+            // contains, plus a known-absent sentinel to exercise the
+            // not-found path.
+            let mut github_modules = hal.manifest_module_names();
+            github_modules.push("NonExistentModule".to_string());
+
+            let queue = VerifyQueue::new(Arc::clone(hal), 3);
+            for module_name in INTERNAL_MODULES {
+                queue.enqueue(*module_name, "InternalManifest");
+            }
+            for module_name in &github_modules {
+                queue.enqueue(module_name.clone(), GITHUB_SOURCE);
+            }
+
+            loop {
+                let info = queue.info();
+                print!(
+                    "\r  Verifying modules... pending={} verifying={} verified={}   ",
+                    info.pending, info.verifying, info.verified
+                );
+                io::stdout().flush().unwrap();
+                if info.pending == 0 && info.verifying == 0 {
+                    break;
+                }
+                thread::sleep(Duration::from_millis(20));
+            }
+            println!();
+
+            let results = queue.join();
+            let find_result = |module_name: &str, signature_source: &str| {
+                results
+                    .iter()
+                    .find(|r| r.module_name == module_name && r.signature_source == signature_source)
+            };
+
             println!("\nInternal Manifest Checks:");
-            print_module_integrity_status_for_command(hal, "SoulOS_Core", "InternalManifest");
-            print_module_integrity_status_for_command(hal, "TensorMemoryDriver", "InternalManifest");
-            print_module_integrity_status_for_command(hal, "RustHAL_Interface", "InternalManifest");
-            
+            for module_name in INTERNAL_MODULES {
+                print_verification_result(module_name, find_result(module_name, "InternalManifest"));
+            }
+
             println!("\nGitHub Blockchain Ledger (Simulated) Checks:");
-            print_module_integrity_status_for_command(hal, "EmotionalResonanceEngine", "GitHubBlockchainLedger (Simulated)");
-            print_module_integrity_status_for_command(hal, "UserInterfaceModule", "GitHubBlockchainLedger (Simulated)");
-            print_module_integrity_status_for_command(hal, "NonExistentModule", "GitHubBlockchainLedger (Simulated)");
+            for module_name in &github_modules {
+                print_verification_result(module_name, find_result(module_name, GITHUB_SOURCE));
+            }
+
+            println!("\nLedger Chain Verification:");
+            match hal.verify_ledger_chain() {
+                Ok(root) => println!("  Chain intact. Final root: {}", digest::to_hex(&root)),
+                Err((idx, module_name)) => println!("  Chain BROKEN at entry #{} ('{}')!", idx, module_name),
+            }
         }
         Commands::Ping => println!("pong!"),
         Commands::InitNpu => {
@@ -162,13 +247,61 @@ fn handle_command(command_enum: Commands, hal: &impl HalTrait) {
                 Err(e) => println!("Error running ONNX model test: {}", e),
             }
         }
+        Commands::RunConformance { suite_dir } => {
+            println!("\nRunning conformance suite at '{}'...", suite_dir);
+            match conformance::run_suite(&suite_dir, hal.as_ref()) {
+                Ok(summary) => {
+                    for report in &summary.reports {
+                        let label = match report.outcome {
+                            CaseOutcome::Passed => "PASS",
+                            CaseOutcome::Failed => "FAIL",
+                            CaseOutcome::Ignored => "IGNORED",
+                            CaseOutcome::Panicked => "PANIC",
+                        };
+                        match &report.detail {
+                            Some(detail) => println!("  [{}] {} ({})", label, report.name, detail),
+                            None => println!("  [{}] {}", label, report.name),
+                        }
+                    }
+
+                    let (passed, failed, ignored, panicked) = summary.counts();
+                    println!(
+                        "\nConformance Summary: {} passed, {} failed, {} ignored, {} panicked ({} total)",
+                        passed, failed, ignored, panicked, summary.reports.len()
+                    );
+
+                    match conformance::write_results_file(&suite_dir, &summary) {
+                        Ok(path) => println!("Results written to '{}'.", path.display()),
+                        Err(e) => println!("Error writing results file: {}", e),
+                    }
+                }
+                Err(e) => println!("Error running conformance suite: {}", e),
+            }
+        }
+        Commands::NegotiateModule { module_name, manifest_version, protocol_version } => {
+            println!(
+                "\nNegotiating module '{}' at manifest_version={}, protocol_version={}...",
+                module_name, manifest_version, protocol_version
+            );
+            match hal.negotiate_module(&module_name, manifest_version, protocol_version) {
+                Ok(accepted) => println!(
+                    "Accepted: chain '{}', manifest v{}, protocol v{}",
+                    accepted.chain_name, accepted.manifest_version, accepted.protocol_version
+                ),
+                Err(nack) => println!("Nacked: {}", nack),
+            }
+        }
     }
 }
 
 fn main() {
     setup_panic!(); // Initialize human-panic
 
-    let hal = MockHal::new(); // Initialize HAL
+    let startup_cli = Cli::parse();
+    let hal: Arc<dyn HalTrait + Send + Sync> = match &startup_cli.manifest {
+        Some(path) => Arc::new(MockHal::from_manifest(path)),
+        None => Arc::new(MockHal::new()),
+    };
 
     // --- First Boot Sequence ---
     println!("***************************************************");
@@ -180,9 +313,9 @@ fn main() {
     println!("Initializing System...");
 
     println!("\nPerforming initial system integrity check...");
-    print_module_integrity_status(&hal, "SoulOS_Core", "InternalManifest");
-    print_module_integrity_status(&hal, "TensorMemoryDriver", "InternalManifest");
-    print_module_integrity_status(&hal, "RustHAL_Interface", "InternalManifest");
+    print_module_integrity_status(hal.as_ref(), "SoulOS_Core", "InternalManifest");
+    print_module_integrity_status(hal.as_ref(), "TensorMemoryDriver", "InternalManifest");
+    print_module_integrity_status(hal.as_ref(), "RustHAL_Interface", "InternalManifest");
 
 
     println!("\nFetching initial system status...");
@@ -238,9 +371,17 @@ fn main() {
                 }
             }
             Err(e) => {
+                if e.kind() == clap::error::ErrorKind::InvalidSubcommand {
+                    if let Some(typed) = trimmed_line.split_whitespace().next() {
+                        if let Some(suggestion) = suggest_command(typed) {
+                            println!("did you mean '{}'?", suggestion);
+                        }
+                    }
+                }
+
                 if let Err(print_err) = e.print() {
                     eprintln!("Error displaying command parse error: {}", print_err);
-                    eprintln!("Original error was: {}", e.to_string()); 
+                    eprintln!("Original error was: {}", e.to_string());
                 }
             }
         }
@@ -0,0 +1,120 @@
+//! Loadable module manifest config, read from a small TOML subset rather
+//! than pulling in a full TOML crate — similar in spirit to how the
+//! Parity/OpenEthereum "config-files" work splits static defaults from a
+//! file an operator can hand-edit.
+//!
+//! ```toml
+//! [SoulOS_Core]
+//! source_url = "gh://soulware/core/v1.0"
+//! content_hash = "5f32dd6a..."
+//! chain_name = "souldos_mainnet"
+//! manifest_version = 1
+//! protocol_version = 1
+//! ```
+//!
+//! Each `[ModuleName]` section becomes one manifest entry; unknown keys are
+//! ignored so the format can grow without breaking old config files.
+//! `chain_name`/`manifest_version`/`protocol_version` are optional and fall
+//! back to [`DEFAULT_CHAIN_NAME`]/`1`/`1` when omitted or unparsable.
+
+use std::fs;
+
+/// Fallback `chain_name` for manifest entries that don't specify one.
+pub const DEFAULT_CHAIN_NAME: &str = "unknown";
+
+pub struct ManifestEntryConfig {
+    pub module_name: String,
+    pub source_url: String,
+    pub content_hash_hex: String,
+    pub chain_name: String,
+    pub manifest_version: u16,
+    pub protocol_version: u16,
+}
+
+/// Reads and parses the manifest config file at `path`.
+pub fn load(path: &str) -> Result<Vec<ManifestEntryConfig>, String> {
+    let contents = fs::read_to_string(path).map_err(|e| format!("could not read manifest '{}': {}", path, e))?;
+    parse(&contents)
+}
+
+/// Accumulator for the section currently being parsed.
+struct PendingEntry {
+    module_name: String,
+    source_url: String,
+    content_hash_hex: String,
+    chain_name: String,
+    manifest_version: u16,
+    protocol_version: u16,
+}
+
+impl PendingEntry {
+    fn new(module_name: String) -> Self {
+        PendingEntry {
+            module_name,
+            source_url: String::new(),
+            content_hash_hex: String::new(),
+            chain_name: DEFAULT_CHAIN_NAME.to_string(),
+            manifest_version: 1,
+            protocol_version: 1,
+        }
+    }
+
+    fn into_config(self) -> ManifestEntryConfig {
+        ManifestEntryConfig {
+            module_name: self.module_name,
+            source_url: self.source_url,
+            content_hash_hex: self.content_hash_hex,
+            chain_name: self.chain_name,
+            manifest_version: self.manifest_version,
+            protocol_version: self.protocol_version,
+        }
+    }
+}
+
+fn parse(contents: &str) -> Result<Vec<ManifestEntryConfig>, String> {
+    let mut entries = Vec::new();
+    let mut current: Option<PendingEntry> = None;
+
+    for raw_line in contents.lines() {
+        let line = raw_line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+
+        if line.starts_with('[') && line.ends_with(']') {
+            if let Some(pending) = current.take() {
+                entries.push(pending.into_config());
+            }
+            current = Some(PendingEntry::new(line[1..line.len() - 1].trim().to_string()));
+            continue;
+        }
+
+        if let Some(pending) = current.as_mut() {
+            if let Some((key, value)) = line.split_once('=') {
+                let value = value.trim().trim_matches('"');
+                match key.trim() {
+                    "source_url" => pending.source_url = value.to_string(),
+                    "content_hash" => pending.content_hash_hex = value.to_string(),
+                    "chain_name" => pending.chain_name = value.to_string(),
+                    "manifest_version" => {
+                        if let Ok(v) = value.parse() {
+                            pending.manifest_version = v;
+                        }
+                    }
+                    "protocol_version" => {
+                        if let Ok(v) = value.parse() {
+                            pending.protocol_version = v;
+                        }
+                    }
+                    _ => {}
+                }
+            }
+        }
+    }
+
+    if let Some(pending) = current.take() {
+        entries.push(pending.into_config());
+    }
+
+    Ok(entries)
+}
@@ -0,0 +1,76 @@
+//! "Did you mean...?" suggestions for unrecognized REPL input, the way
+//! cargo suggests a subcommand when you typo one.
+
+/// Classic DP edit distance between `a` and `b`, using a single rolling
+/// row rather than a full `m x n` matrix.
+pub fn levenshtein(a: &str, b: &str) -> usize {
+    let a: Vec<char> = a.chars().collect();
+    let b: Vec<char> = b.chars().collect();
+    let n = b.len();
+
+    let mut d: Vec<usize> = (0..=n).collect();
+    for (i, &ca) in a.iter().enumerate() {
+        let mut prev = d[0];
+        d[0] = i + 1;
+        for j in 1..=n {
+            let old_dj = d[j];
+            let cost = if ca != b[j - 1] { 1 } else { 0 };
+            d[j] = (d[j] + 1).min(d[j - 1] + 1).min(prev + cost);
+            prev = old_dj;
+        }
+    }
+    d[n]
+}
+
+/// Finds the closest candidate to `typed`, if any candidate is close
+/// enough to be a plausible typo (edit distance <= 2, or <= a third of
+/// the typed token's length for longer commands).
+pub fn closest_match<'a>(typed: &str, candidates: impl IntoIterator<Item = &'a str>) -> Option<&'a str> {
+    candidates
+        .into_iter()
+        .map(|candidate| (levenshtein(typed, candidate), candidate))
+        .min_by_key(|(distance, _)| *distance)
+        .filter(|(distance, _)| *distance <= 2 || *distance <= (typed.len() / 3).max(2))
+        .map(|(_, candidate)| candidate)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn levenshtein_identical_strings() {
+        assert_eq!(levenshtein("status", "status"), 0);
+    }
+
+    #[test]
+    fn levenshtein_empty_strings() {
+        assert_eq!(levenshtein("", ""), 0);
+        assert_eq!(levenshtein("status", ""), 6);
+        assert_eq!(levenshtein("", "status"), 6);
+    }
+
+    #[test]
+    fn levenshtein_single_edits() {
+        assert_eq!(levenshtein("status", "statu"), 1);
+        assert_eq!(levenshtein("status", "statux"), 1);
+        assert_eq!(levenshtein("status", "statys"), 1);
+    }
+
+    #[test]
+    fn levenshtein_known_distance() {
+        assert_eq!(levenshtein("kitten", "sitting"), 3);
+    }
+
+    #[test]
+    fn closest_match_picks_nearest_candidate() {
+        let candidates = ["status", "verify", "conform"];
+        assert_eq!(closest_match("staus", candidates), Some("status"));
+    }
+
+    #[test]
+    fn closest_match_rejects_unrelated_input() {
+        let candidates = ["status", "verify", "conform"];
+        assert_eq!(closest_match("xyzxyzxyz", candidates), None);
+    }
+}
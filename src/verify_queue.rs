@@ -0,0 +1,170 @@
+//! Concurrent module verification queue, modeled on the shape of
+//! OpenEthereum's `BlockQueue`: a shared job deque guarded by a `Condvar`
+//! the workers wait on (`more_to_verify`), a second `Condvar` the caller
+//! blocks on until everything has drained (`empty`), and an in-flight set
+//! so the same `(module, signature_source)` pair can't be queued twice
+//! while it's being verified.
+//!
+//! All mutable queue state lives behind a single `Mutex<QueueState>` so the
+//! pending -> verifying transition is atomic and both condvars' predicates
+//! are evaluated under one lock; splitting that state across several
+//! mutexes is what let a job be briefly invisible to both `pending` and
+//! `verifying` counts.
+
+use std::collections::{HashSet, VecDeque};
+use std::sync::{Arc, Condvar, Mutex};
+use std::thread::{self, JoinHandle};
+
+use crate::hal::HalTrait;
+
+struct ModuleJob {
+    module_name: String,
+    signature_source: String,
+}
+
+/// Outcome of verifying a single queued `(module, signature_source)` pair.
+#[derive(Clone)]
+pub struct VerificationResult {
+    pub module_name: String,
+    pub signature_source: String,
+    pub outcome: Result<bool, String>,
+}
+
+/// Point-in-time snapshot of queue occupancy, for printing a live progress
+/// line while a large batch of modules drains.
+#[derive(Clone, Copy)]
+pub struct QueueInfo {
+    pub pending: usize,
+    pub verifying: usize,
+    pub verified: usize,
+}
+
+struct QueueState {
+    jobs: VecDeque<ModuleJob>,
+    in_flight: HashSet<(String, String)>,
+    verifying: usize,
+    results: Vec<VerificationResult>,
+    closed: bool,
+}
+
+struct Shared {
+    state: Mutex<QueueState>,
+    more_to_verify: Condvar,
+    empty: Condvar,
+}
+
+/// A pool of verifier threads draining a shared queue of module-verification
+/// jobs against a `HalTrait` implementation.
+pub struct VerifyQueue {
+    shared: Arc<Shared>,
+    workers: Vec<JoinHandle<()>>,
+}
+
+impl VerifyQueue {
+    pub fn new(hal: Arc<dyn HalTrait + Send + Sync>, worker_count: usize) -> Self {
+        let shared = Arc::new(Shared {
+            state: Mutex::new(QueueState {
+                jobs: VecDeque::new(),
+                in_flight: HashSet::new(),
+                verifying: 0,
+                results: Vec::new(),
+                closed: false,
+            }),
+            more_to_verify: Condvar::new(),
+            empty: Condvar::new(),
+        });
+
+        let workers = (0..worker_count.max(1))
+            .map(|_| {
+                let shared = Arc::clone(&shared);
+                let hal = Arc::clone(&hal);
+                thread::spawn(move || Self::worker_loop(shared, hal))
+            })
+            .collect();
+
+        VerifyQueue { shared, workers }
+    }
+
+    /// Queues a `(module, signature_source)` pair for verification unless
+    /// that exact pair is already pending or in-flight. Distinct sources
+    /// for the same module (e.g. `InternalManifest` vs the GitHub ledger)
+    /// are independent jobs, each checked and reported separately.
+    pub fn enqueue(&self, module_name: impl Into<String>, signature_source: impl Into<String>) {
+        let module_name = module_name.into();
+        let signature_source = signature_source.into();
+
+        let mut state = self.shared.state.lock().unwrap();
+        if !state.in_flight.insert((module_name.clone(), signature_source.clone())) {
+            return;
+        }
+        state.jobs.push_back(ModuleJob { module_name, signature_source });
+        drop(state);
+
+        self.shared.more_to_verify.notify_one();
+    }
+
+    /// Current pending/verifying/verified counts, for progress reporting.
+    pub fn info(&self) -> QueueInfo {
+        let state = self.shared.state.lock().unwrap();
+        QueueInfo {
+            pending: state.jobs.len(),
+            verifying: state.verifying,
+            verified: state.results.len(),
+        }
+    }
+
+    /// Blocks until every queued job has drained, then shuts the worker
+    /// threads down and returns all results gathered so far.
+    pub fn join(self) -> Vec<VerificationResult> {
+        {
+            let mut state = self.shared.state.lock().unwrap();
+            while !state.jobs.is_empty() || state.verifying > 0 {
+                state = self.shared.empty.wait(state).unwrap();
+            }
+            state.closed = true;
+        }
+
+        self.shared.more_to_verify.notify_all();
+        for worker in self.workers {
+            let _ = worker.join();
+        }
+
+        self.shared.state.lock().unwrap().results.clone()
+    }
+
+    fn worker_loop(shared: Arc<Shared>, hal: Arc<dyn HalTrait + Send + Sync>) {
+        loop {
+            let job = {
+                let mut state = shared.state.lock().unwrap();
+                loop {
+                    if let Some(job) = state.jobs.pop_front() {
+                        // Move pending -> verifying atomically, under the
+                        // same lock the `empty` predicate reads.
+                        state.verifying += 1;
+                        break Some(job);
+                    }
+                    if state.closed {
+                        break None;
+                    }
+                    state = shared.more_to_verify.wait(state).unwrap();
+                }
+            };
+
+            let Some(job) = job else { return };
+
+            let outcome = hal.verify_module_signature(&job.module_name, &job.signature_source);
+
+            let mut state = shared.state.lock().unwrap();
+            state.verifying -= 1;
+            state.in_flight.remove(&(job.module_name.clone(), job.signature_source.clone()));
+            state.results.push(VerificationResult {
+                module_name: job.module_name,
+                signature_source: job.signature_source,
+                outcome,
+            });
+            drop(state);
+
+            shared.empty.notify_all();
+        }
+    }
+}